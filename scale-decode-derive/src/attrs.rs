@@ -0,0 +1,267 @@
+// Copyright (C) 2023 Parity Technologies (UK) Ltd. (admin@parity.io)
+// This file is a part of the scale-decode crate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//         http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::case::RenameRule;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{punctuated::Punctuated, spanned::Spanned, DeriveInput, Meta, Token};
+
+/// Container-level `#[codec(..)]` attributes.
+#[derive(Default)]
+struct ContainerAttrs {
+    rename_all: Option<RenameRule>,
+}
+
+/// Field-level `#[codec(..)]` attributes.
+#[derive(Default)]
+struct FieldAttrs {
+    rename: Option<String>,
+    skip: bool,
+    default: bool,
+}
+
+fn codec_metas(attrs: &[syn::Attribute]) -> syn::Result<Vec<Meta>> {
+    let mut metas = vec![];
+    for attr in attrs {
+        if !attr.path().is_ident("codec") {
+            continue;
+        }
+        let nested = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+        metas.extend(nested);
+    }
+    Ok(metas)
+}
+
+fn parse_container_attrs(attrs: &[syn::Attribute]) -> syn::Result<ContainerAttrs> {
+    let mut out = ContainerAttrs::default();
+    for meta in codec_metas(attrs)? {
+        if let Meta::NameValue(nv) = &meta {
+            if nv.path.is_ident("rename_all") {
+                let lit = expect_str_lit(&nv.value)?;
+                out.rename_all = Some(RenameRule::from_str(&lit).ok_or_else(|| {
+                    syn::Error::new(nv.value.span(), format!("unknown rename_all rule '{lit}'"))
+                })?);
+                continue;
+            }
+        }
+        return Err(syn::Error::new(meta.span(), "unrecognised container #[codec(..)] attribute"));
+    }
+    Ok(out)
+}
+
+fn parse_field_attrs(attrs: &[syn::Attribute]) -> syn::Result<FieldAttrs> {
+    let mut out = FieldAttrs::default();
+    for meta in codec_metas(attrs)? {
+        match &meta {
+            Meta::NameValue(nv) if nv.path.is_ident("rename") => {
+                out.rename = Some(expect_str_lit(&nv.value)?);
+            }
+            Meta::Path(p) if p.is_ident("skip") => out.skip = true,
+            Meta::Path(p) if p.is_ident("default") => out.default = true,
+            _ => {
+                return Err(syn::Error::new(
+                    meta.span(),
+                    "unrecognised field #[codec(..)] attribute",
+                ))
+            }
+        }
+    }
+    Ok(out)
+}
+
+fn expect_str_lit(expr: &syn::Expr) -> syn::Result<String> {
+    if let syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(s), .. }) = expr {
+        Ok(s.value())
+    } else {
+        Err(syn::Error::new(expr.span(), "expected a string literal"))
+    }
+}
+
+/// The on-chain name we'll look for a given Rust field under, taking any `rename`/`rename_all`
+/// attributes into account. `rust_name` is the raw `Ident::to_string()` of the field, so a
+/// leading `r#` (present on raw identifiers like `r#type`) is stripped first; otherwise it'd
+/// leak into the default on-chain name and into whatever `rename_all` produces from it.
+fn field_target_name(container: &ContainerAttrs, field: &FieldAttrs, rust_name: &str) -> String {
+    if let Some(rename) = &field.rename {
+        return rename.clone();
+    }
+    let rust_name = rust_name.trim_start_matches("r#");
+    if let Some(rule) = container.rename_all {
+        return rule.apply(rust_name);
+    }
+    rust_name.to_owned()
+}
+
+pub fn generate_decode_as_type_impl(input: DeriveInput) -> syn::Result<TokenStream> {
+    let container_attrs = parse_container_attrs(&input.attrs)?;
+    let ident = &input.ident;
+
+    let syn::Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(
+            input.span(),
+            "#[derive(DecodeAsType)] currently only supports structs with named fields",
+        ));
+    };
+    let syn::Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new(
+            data.fields.span(),
+            "#[derive(DecodeAsType)] currently only supports structs with named fields",
+        ));
+    };
+
+    let mut field_inits = vec![];
+    for field in &fields.named {
+        let field_attrs = parse_field_attrs(&field.attrs)?;
+        let field_ident = field.ident.as_ref().expect("named field");
+        let ty = &field.ty;
+
+        if field_attrs.skip {
+            field_inits.push(quote! { #field_ident: ::core::default::Default::default() });
+            continue;
+        }
+
+        let target_name = field_target_name(&container_attrs, &field_attrs, &field_ident.to_string());
+        let composite_field_var = format_ident!("__field_{field_ident}");
+
+        let missing_field_handling = if field_attrs.default {
+            quote! { ::core::default::Default::default() }
+        } else {
+            quote! {
+                return Err(::scale_decode::Error::custom_string(::alloc::format!(
+                    "Field '{}' not found in the source composite type",
+                    #target_name
+                )))
+            }
+        };
+
+        field_inits.push(quote! {
+            #field_ident: match value.find_field(#target_name) {
+                Some(#composite_field_var) => #composite_field_var.decode_as_type::<#ty>()?,
+                None => #missing_field_handling,
+            }
+        });
+    }
+
+    Ok(quote! {
+        impl ::scale_decode::DecodeAsType for #ident {
+            fn decode_as_type<__R: ::scale_decode::TypeResolver>(
+                input: &mut &[u8],
+                type_id: __R::TypeId,
+                types: &__R,
+            ) -> ::core::result::Result<Self, ::scale_decode::Error> {
+                // A throwaway `Visitor` impl that only knows how to build `#ident` out of a
+                // composite type; this is what actually drives the decoding via `decode_with_visitor`.
+                struct __Visitor<__R>(::core::marker::PhantomData<fn() -> __R>);
+
+                impl<__R: ::scale_decode::TypeResolver> ::scale_decode::visitor::Visitor for __Visitor<__R> {
+                    type TypeResolver = __R;
+                    type Value<'scale, 'info> = #ident;
+                    type Error = ::scale_decode::Error;
+
+                    fn visit_composite<'scale, 'info>(
+                        self,
+                        value: &mut ::scale_decode::visitor::types::Composite<'scale, 'info, __R>,
+                    ) -> ::core::result::Result<Self::Value<'scale, 'info>, Self::Error> {
+                        ::core::result::Result::Ok(#ident {
+                            #(#field_inits),*
+                        })
+                    }
+                }
+
+                ::scale_decode::visitor::decode_with_visitor(
+                    input,
+                    type_id,
+                    types,
+                    __Visitor(::core::marker::PhantomData),
+                )
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand(input: proc_macro2::TokenStream) -> String {
+        let derive_input: DeriveInput = syn::parse2(input).unwrap();
+        generate_decode_as_type_impl(derive_input).unwrap().to_string()
+    }
+
+    #[test]
+    fn raw_identifier_field_defaults_to_its_name_without_the_r_hash_prefix() {
+        let out = expand(quote! {
+            struct Foo { r#type: u8 }
+        });
+        assert!(out.contains("find_field (\"type\""), "expected bare 'type', got: {out}");
+        assert!(!out.contains("\"r#type\""));
+    }
+
+    #[test]
+    fn rename_all_is_applied_to_the_stripped_raw_identifier_name() {
+        let out = expand(quote! {
+            #[codec(rename_all = "PascalCase")]
+            struct Foo { r#type: u8 }
+        });
+        assert!(out.contains("find_field (\"Type\""), "expected 'Type', got: {out}");
+    }
+
+    #[test]
+    fn explicit_rename_takes_precedence_over_rename_all() {
+        let out = expand(quote! {
+            #[codec(rename_all = "PascalCase")]
+            struct Foo {
+                #[codec(rename = "custom")]
+                bar: u8,
+            }
+        });
+        assert!(out.contains("find_field (\"custom\""), "expected 'custom', got: {out}");
+        assert!(!out.contains("\"Bar\""));
+    }
+
+    #[test]
+    fn skip_fields_are_default_initialised_without_looking_them_up() {
+        let out = expand(quote! {
+            struct Foo {
+                #[codec(skip)]
+                bar: u8,
+            }
+        });
+        assert!(out.contains("Default :: default ()"));
+        assert!(!out.contains("find_field (\"bar\""));
+    }
+
+    #[test]
+    fn default_fields_fall_back_instead_of_erroring_when_missing() {
+        let out = expand(quote! {
+            struct Foo {
+                #[codec(default)]
+                bar: u8,
+            }
+        });
+        assert!(out.contains("find_field (\"bar\""));
+        assert!(out.contains("Default :: default ()"));
+        assert!(!out.contains("not found in the source composite type"));
+    }
+
+    #[test]
+    fn missing_required_field_error_uses_fully_qualified_alloc_format() {
+        let out = expand(quote! {
+            struct Foo { bar: u8 }
+        });
+        assert!(out.contains(":: alloc :: format !"));
+        assert!(!out.replace(":: alloc :: format !", "").contains("format !"));
+    }
+}
@@ -0,0 +1,114 @@
+// Copyright (C) 2023 Parity Technologies (UK) Ltd. (admin@parity.io)
+// This file is a part of the scale-decode crate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//         http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A small, self-contained case-conversion table, applied to field names when matching
+//! against a `#[codec(rename_all = "...")]` container attribute.
+
+/// The case conventions that `#[codec(rename_all = "...")]` understands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenameRule {
+    /// `my_field_name`
+    SnakeCase,
+    /// `myFieldName`
+    CamelCase,
+    /// `MyFieldName`
+    PascalCase,
+    /// `my-field-name`
+    KebabCase,
+    /// `MY_FIELD_NAME`
+    ScreamingSnakeCase,
+}
+
+impl RenameRule {
+    /// Parse the string given to `#[codec(rename_all = "...")]` into a [`RenameRule`].
+    pub fn from_str(s: &str) -> Option<RenameRule> {
+        let rule = match s {
+            "snake_case" => RenameRule::SnakeCase,
+            "camelCase" => RenameRule::CamelCase,
+            "PascalCase" => RenameRule::PascalCase,
+            "kebab-case" => RenameRule::KebabCase,
+            "SCREAMING_SNAKE_CASE" => RenameRule::ScreamingSnakeCase,
+            _ => return None,
+        };
+        Some(rule)
+    }
+
+    /// Apply this rule to a Rust field name (assumed to already be `snake_case`, since that's
+    /// the only valid case for a Rust identifier) to produce the on-chain name we expect to see.
+    pub fn apply(&self, field_name: &str) -> String {
+        let words: Vec<&str> = field_name.split('_').filter(|w| !w.is_empty()).collect();
+        match self {
+            RenameRule::SnakeCase => words.join("_"),
+            RenameRule::KebabCase => words.join("-"),
+            RenameRule::ScreamingSnakeCase => {
+                words.iter().map(|w| w.to_uppercase()).collect::<Vec<_>>().join("_")
+            }
+            RenameRule::PascalCase => words.iter().map(|w| capitalize(w)).collect(),
+            RenameRule::CamelCase => {
+                let mut out = String::new();
+                for (idx, word) in words.iter().enumerate() {
+                    if idx == 0 {
+                        out.push_str(word);
+                    } else {
+                        out.push_str(&capitalize(word));
+                    }
+                }
+                out
+            }
+        }
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_known_rule_names() {
+        assert_eq!(RenameRule::from_str("snake_case"), Some(RenameRule::SnakeCase));
+        assert_eq!(RenameRule::from_str("camelCase"), Some(RenameRule::CamelCase));
+        assert_eq!(RenameRule::from_str("PascalCase"), Some(RenameRule::PascalCase));
+        assert_eq!(RenameRule::from_str("kebab-case"), Some(RenameRule::KebabCase));
+        assert_eq!(
+            RenameRule::from_str("SCREAMING_SNAKE_CASE"),
+            Some(RenameRule::ScreamingSnakeCase)
+        );
+        assert_eq!(RenameRule::from_str("not_a_rule"), None);
+    }
+
+    #[test]
+    fn applies_rules_to_a_multi_word_field_name() {
+        let field = "my_field_name";
+        assert_eq!(RenameRule::SnakeCase.apply(field), "my_field_name");
+        assert_eq!(RenameRule::CamelCase.apply(field), "myFieldName");
+        assert_eq!(RenameRule::PascalCase.apply(field), "MyFieldName");
+        assert_eq!(RenameRule::KebabCase.apply(field), "my-field-name");
+        assert_eq!(RenameRule::ScreamingSnakeCase.apply(field), "MY_FIELD_NAME");
+    }
+
+    #[test]
+    fn applies_rules_to_a_single_word_field_name() {
+        assert_eq!(RenameRule::CamelCase.apply("foo"), "foo");
+        assert_eq!(RenameRule::PascalCase.apply("foo"), "Foo");
+    }
+}
@@ -0,0 +1,48 @@
+// Copyright (C) 2023 Parity Technologies (UK) Ltd. (admin@parity.io)
+// This file is a part of the scale-decode crate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//         http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This crate provides the `#[derive(DecodeAsType)]` macro, which generates a `DecodeAsType`
+//! impl for a struct with named fields by matching each of its fields against the SCALE composite
+//! fields of the same name; enums and tuple structs are not supported. `#[codec(..)]` attributes (handled by the [`attrs`] module) let the target
+//! Rust names diverge from the on-chain field names.
+
+mod attrs;
+mod case;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+/// Derive `scale_decode::DecodeAsType` for a struct with named fields, matching fields against
+/// the equivalent composite fields of the SCALE type being decoded. Enums and tuple structs are
+/// rejected with a compile error.
+///
+/// Container and field attributes, given inside `#[codec(..)]`, tweak how fields are matched:
+///
+/// - `#[codec(rename_all = "camelCase")]` (container): convert every field name using the given
+///   case convention (`snake_case`, `camelCase`, `PascalCase`, `kebab-case` or
+///   `SCREAMING_SNAKE_CASE`) before comparing it to the on-chain field name.
+/// - `#[codec(rename = "...")]` (field): match this field against a specific on-chain name,
+///   overriding any `rename_all` that applies at the container level.
+/// - `#[codec(skip)]` (field): don't look for this field at all; populate it from
+///   `Default::default()`.
+/// - `#[codec(default)]` (field): if the on-chain composite doesn't have a matching field,
+///   populate this one from `Default::default()` instead of returning an error.
+#[proc_macro_derive(DecodeAsType, attributes(codec))]
+pub fn derive_decode_as_type(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    attrs::generate_decode_as_type_impl(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
@@ -0,0 +1,263 @@
+// Copyright (C) 2023 Parity Technologies (UK) Ltd. (admin@parity.io)
+// This file is a part of the scale-decode crate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//         http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module defines the [`TypeResolver`] trait, which abstracts over the source of type
+//! information used to decode SCALE bytes. By default we decode against a [`scale_info::PortableRegistry`],
+//! but implementing this trait for some other type store (for instance a compressed metadata
+//! format, or a custom in-memory registry) allows the rest of this crate to decode against it
+//! without first converting it into a `PortableRegistry`.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use scale_info::{PortableRegistry, TypeDefPrimitive};
+
+/// A single field belonging to a composite type or enum variant, as reported to a
+/// [`ResolvedTypeVisitor`]. The name and ID are owned rather than borrowed: a [`TypeResolver`]
+/// impl backed by a [`PortableRegistry`] has to build this information fresh on every `resolve()`
+/// call (its own field lists don't have a shape compatible with `FieldInfo`), so there's no
+/// lifetime that a borrowed version of this type could be tied to that would also be long enough
+/// for [`super::Visitor::Value`] to escape the `resolve()` call with. Owning the data keeps
+/// resolvers free to build it however they like, and keeps [`crate::visitor::types::Composite`]
+/// (which holds onto a list of these across multiple [`crate::visitor::types::Composite::decode_item`]
+/// calls) from needing to borrow from something that's only valid for the duration of `resolve()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldInfo<Id> {
+    /// The name of the field, or `None` if the field is unnamed (ie part of a tuple-like type).
+    pub name: Option<String>,
+    /// The type of the field.
+    pub id: Id,
+}
+
+/// A single variant belonging to an enum (variant) type, as reported to a [`ResolvedTypeVisitor`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariantInfo<Id> {
+    /// The index used to SCALE encode this variant.
+    pub index: u8,
+    /// The name of the variant.
+    pub name: String,
+    /// The fields contained in this variant.
+    pub fields: Vec<FieldInfo<Id>>,
+}
+
+/// The bit ordering described by a bit sequence type's "order type". [`TypeResolver`] impls
+/// resolve this themselves (for a [`PortableRegistry`], that means inspecting the path of the
+/// resolved order type), so that [`ResolvedTypeVisitor::visit_bit_sequence`] doesn't need to
+/// resolve any further type IDs itself in order to build a `scale_bits` format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitOrder {
+    /// The least significant bit is first.
+    Lsb0,
+    /// The most significant bit is first.
+    Msb0,
+}
+
+/// A description of the shape of a single type, as reported by [`TypeResolver::resolve`]. Implement
+/// this trait on some type of your choosing and hand it to [`TypeResolver::resolve`]; the appropriate
+/// method will be called back with the information needed to continue decoding.
+pub trait ResolvedTypeVisitor<Id> {
+    /// The value handed back once the shape of the type has been visited.
+    type Value;
+
+    /// The type is a composite type with the given fields (empty if the type is a unit type).
+    fn visit_composite(self, _fields: &[FieldInfo<Id>]) -> Self::Value
+    where
+        Self: Sized,
+    {
+        self.visit_unknown()
+    }
+    /// The type is a variant (enum) type with the given set of variants.
+    fn visit_variant(self, _variants: &[VariantInfo<Id>]) -> Self::Value
+    where
+        Self: Sized,
+    {
+        self.visit_unknown()
+    }
+    /// The type is a sequence (eg a `Vec<T>`) whose elements have the given type ID.
+    fn visit_sequence(self, _inner: Id) -> Self::Value {
+        self.visit_unknown()
+    }
+    /// The type is a fixed length array of the given length, whose elements have the given type ID.
+    fn visit_array(self, _inner: Id, _len: usize) -> Self::Value {
+        self.visit_unknown()
+    }
+    /// The type is a tuple of the given types.
+    fn visit_tuple(self, _type_ids: &[Id]) -> Self::Value
+    where
+        Self: Sized,
+    {
+        self.visit_unknown()
+    }
+    /// The type is a primitive type like `u8`, `bool`, `str` and so on.
+    fn visit_primitive(self, _primitive: TypeDefPrimitive) -> Self::Value {
+        self.visit_unknown()
+    }
+    /// The type is SCALE compact encoded, and itself describes the given type ID.
+    fn visit_compact(self, _inner: Id) -> Self::Value {
+        self.visit_unknown()
+    }
+    /// The type is a bit sequence, with the given store primitive and bit ordering.
+    fn visit_bit_sequence(self, _store: TypeDefPrimitive, _order: BitOrder) -> Self::Value {
+        self.visit_unknown()
+    }
+    /// Called if none of the more specific `visit_*` methods above are overridden for the shape
+    /// that was actually resolved. The default implementations of those methods delegate here.
+    fn visit_unknown(self) -> Self::Value;
+}
+
+/// This trait is implemented for anything that can be used to look up information about types,
+/// given some type ID. Implementing this for your own type store allows the rest of this crate to
+/// decode SCALE bytes against it, without first needing to convert it into a [`PortableRegistry`].
+pub trait TypeResolver {
+    /// The type of identifier that this resolver uses to look up types.
+    type TypeId: Clone;
+    /// An error that can be returned if resolving the type fails.
+    type Error: core::fmt::Debug;
+
+    /// Resolve a type ID into a description of its shape, handed to the given visitor.
+    fn resolve<V: ResolvedTypeVisitor<Self::TypeId>>(
+        &self,
+        type_id: &Self::TypeId,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>;
+}
+
+/// An error resolving a type ID in a [`PortableRegistry`]: the ID did not correspond to any type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortableRegistryResolveError(pub u32);
+
+impl core::fmt::Display for PortableRegistryResolveError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Type with ID {} not found in the registry", self.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PortableRegistryResolveError {}
+
+impl TypeResolver for PortableRegistry {
+    type TypeId = u32;
+    type Error = PortableRegistryResolveError;
+
+    fn resolve<V: ResolvedTypeVisitor<Self::TypeId>>(
+        &self,
+        type_id: &Self::TypeId,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        use scale_info::TypeDef;
+
+        let ty = self.resolve(*type_id).ok_or(PortableRegistryResolveError(*type_id))?;
+
+        Ok(match &ty.type_def {
+            TypeDef::Composite(c) => {
+                let fields: Vec<FieldInfo<u32>> = c
+                    .fields
+                    .iter()
+                    .map(|f| FieldInfo { name: f.name().map(|n| n.to_string()), id: f.ty().id() })
+                    .collect();
+                visitor.visit_composite(&fields)
+            }
+            TypeDef::Variant(v) => {
+                let variants: Vec<VariantInfo<u32>> = v
+                    .variants
+                    .iter()
+                    .map(|variant| {
+                        let fields = variant
+                            .fields
+                            .iter()
+                            .map(|f| FieldInfo {
+                                name: f.name().map(|n| n.to_string()),
+                                id: f.ty().id(),
+                            })
+                            .collect();
+                        VariantInfo { index: variant.index, name: variant.name.to_string(), fields }
+                    })
+                    .collect();
+                visitor.visit_variant(&variants)
+            }
+            TypeDef::Sequence(s) => visitor.visit_sequence(s.type_param.id),
+            TypeDef::Array(a) => visitor.visit_array(a.type_param.id, a.len as usize),
+            TypeDef::Tuple(t) => {
+                let ids: Vec<u32> = t.fields.iter().map(|f| f.id).collect();
+                visitor.visit_tuple(&ids)
+            }
+            TypeDef::Primitive(p) => visitor.visit_primitive(p.clone()),
+            TypeDef::Compact(c) => visitor.visit_compact(c.type_param.id),
+            TypeDef::BitSequence(b) => {
+                let store = resolve_store_primitive(self, b.bit_store_type.id)?;
+                let order = resolve_bit_order(self, b.bit_order_type.id);
+                visitor.visit_bit_sequence(store, order)
+            }
+        })
+    }
+}
+
+/// Look up the primitive that a bit sequence's "store type" resolves to (eg `u8`/`u16`/`u32`/`u64`).
+fn resolve_store_primitive(
+    types: &PortableRegistry,
+    id: u32,
+) -> Result<TypeDefPrimitive, PortableRegistryResolveError> {
+    let ty = types.resolve(id).ok_or(PortableRegistryResolveError(id))?;
+    match &ty.type_def {
+        scale_info::TypeDef::Primitive(p) => Ok(p.clone()),
+        _ => Err(PortableRegistryResolveError(id)),
+    }
+}
+
+/// Look up the bit ordering that a bit sequence's "order type" describes, by inspecting the path
+/// of the resolved type (`bitvec::order::Lsb0`/`bitvec::order::Msb0`, or similar). Any order type
+/// whose last path segment isn't `Lsb0`, or which can't be resolved, is treated as [`BitOrder::Msb0`].
+fn resolve_bit_order(types: &PortableRegistry, id: u32) -> BitOrder {
+    let is_lsb0 = types
+        .resolve(id)
+        .and_then(|ty| ty.path.segments.last())
+        .map(|segment| segment == "Lsb0")
+        .unwrap_or(false);
+
+    if is_lsb0 {
+        BitOrder::Lsb0
+    } else {
+        BitOrder::Msb0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn field_info_is_owned_and_can_outlive_a_resolve_call() {
+        // Build a FieldInfo from a temporary String to prove that it doesn't borrow from
+        // whatever the resolver built it from; this is the whole point of owning `name`/`id`
+        // rather than borrowing them, since a `PortableRegistry` only ever has a short-lived
+        // `Vec<FieldInfo<_>>` to hand to `visit_composite` on each `resolve()` call.
+        let field = {
+            let tmp_name = String::from("foo");
+            FieldInfo { name: Some(tmp_name), id: 1u32 }
+        };
+        assert_eq!(field.name.as_deref(), Some("foo"));
+        assert_eq!(field.id, 1);
+    }
+
+    #[test]
+    fn variant_info_carries_owned_fields() {
+        let variant = VariantInfo {
+            index: 0,
+            name: "Foo".to_string(),
+            fields: vec![FieldInfo { name: None, id: 2u32 }],
+        };
+        assert_eq!(variant.fields.len(), 1);
+        assert_eq!(variant.fields[0].id, 2);
+    }
+}
@@ -0,0 +1,844 @@
+// Copyright (C) 2023 Parity Technologies (UK) Ltd. (admin@parity.io)
+// This file is a part of the scale-decode crate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//         http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module exposes a [`Visitor`] trait which can be implemented to describe how to decode
+//! SCALE encoded bytes into some arbitrary type, given a description of the shape of the type
+//! obtained from a [`crate::TypeResolver`].
+
+pub mod types;
+
+use crate::TypeResolver;
+use scale_info::{PortableRegistry, TypeDefPrimitive};
+use types::{Array, BitSequence, Composite, Sequence, Str, Tuple, Variant};
+
+/// An error emitted when something goes wrong decoding SCALE bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodeError(alloc::string::String);
+
+impl DecodeError {
+    /// Construct a [`DecodeError`] from anything that can be turned into a string.
+    pub fn from_string(s: impl Into<alloc::string::String>) -> DecodeError {
+        DecodeError(s.into())
+    }
+}
+
+impl core::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
+
+/// This trait is implemented for things that can decode SCALE encoded bytes into some given
+/// shape, given a description of the shape of the type being decoded. One method is called per
+/// possible shape; implement whichever ones you care about and leave the rest with their
+/// (erroring) default implementations.
+pub trait Visitor: Sized {
+    /// The type of resolver used to look up type information while decoding.
+    type TypeResolver: TypeResolver;
+    /// The type handed back from a successful decode.
+    type Value<'scale, 'info>;
+    /// The error returned if a decode isn't handled/succeed.
+    type Error: From<DecodeError>;
+
+    /// Called when a bool is seen in the input bytes.
+    fn visit_bool<'scale, 'info>(
+        self,
+        _value: bool,
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Err(DecodeError::from_string("visit_bool not implemented").into())
+    }
+    /// Called when a char is seen in the input bytes.
+    fn visit_char<'scale, 'info>(
+        self,
+        _value: char,
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Err(DecodeError::from_string("visit_char not implemented").into())
+    }
+    /// Called when a u8 is seen in the input bytes.
+    fn visit_u8<'scale, 'info>(
+        self,
+        _value: u8,
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Err(DecodeError::from_string("visit_u8 not implemented").into())
+    }
+    /// Called when a u16 is seen in the input bytes.
+    fn visit_u16<'scale, 'info>(
+        self,
+        _value: u16,
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Err(DecodeError::from_string("visit_u16 not implemented").into())
+    }
+    /// Called when a u32 is seen in the input bytes.
+    fn visit_u32<'scale, 'info>(
+        self,
+        _value: u32,
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Err(DecodeError::from_string("visit_u32 not implemented").into())
+    }
+    /// Called when a u64 is seen in the input bytes.
+    fn visit_u64<'scale, 'info>(
+        self,
+        _value: u64,
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Err(DecodeError::from_string("visit_u64 not implemented").into())
+    }
+    /// Called when a u128 is seen in the input bytes.
+    fn visit_u128<'scale, 'info>(
+        self,
+        _value: u128,
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Err(DecodeError::from_string("visit_u128 not implemented").into())
+    }
+    /// Called when a u256 is seen in the input bytes.
+    fn visit_u256<'scale, 'info>(
+        self,
+        _value: &'scale [u8; 32],
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Err(DecodeError::from_string("visit_u256 not implemented").into())
+    }
+    /// Called when an i8 is seen in the input bytes.
+    fn visit_i8<'scale, 'info>(
+        self,
+        _value: i8,
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Err(DecodeError::from_string("visit_i8 not implemented").into())
+    }
+    /// Called when an i16 is seen in the input bytes.
+    fn visit_i16<'scale, 'info>(
+        self,
+        _value: i16,
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Err(DecodeError::from_string("visit_i16 not implemented").into())
+    }
+    /// Called when an i32 is seen in the input bytes.
+    fn visit_i32<'scale, 'info>(
+        self,
+        _value: i32,
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Err(DecodeError::from_string("visit_i32 not implemented").into())
+    }
+    /// Called when an i64 is seen in the input bytes.
+    fn visit_i64<'scale, 'info>(
+        self,
+        _value: i64,
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Err(DecodeError::from_string("visit_i64 not implemented").into())
+    }
+    /// Called when an i128 is seen in the input bytes.
+    fn visit_i128<'scale, 'info>(
+        self,
+        _value: i128,
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Err(DecodeError::from_string("visit_i128 not implemented").into())
+    }
+    /// Called when an i256 is seen in the input bytes.
+    fn visit_i256<'scale, 'info>(
+        self,
+        _value: &'scale [u8; 32],
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Err(DecodeError::from_string("visit_i256 not implemented").into())
+    }
+    /// Called when a compact-encoded u8 is seen in the input bytes.
+    fn visit_compact_u8<'scale, 'info>(
+        self,
+        _value: u8,
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Err(DecodeError::from_string("visit_compact_u8 not implemented").into())
+    }
+    /// Called when a compact-encoded u16 is seen in the input bytes.
+    fn visit_compact_u16<'scale, 'info>(
+        self,
+        _value: u16,
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Err(DecodeError::from_string("visit_compact_u16 not implemented").into())
+    }
+    /// Called when a compact-encoded u32 is seen in the input bytes.
+    fn visit_compact_u32<'scale, 'info>(
+        self,
+        _value: u32,
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Err(DecodeError::from_string("visit_compact_u32 not implemented").into())
+    }
+    /// Called when a compact-encoded u64 is seen in the input bytes.
+    fn visit_compact_u64<'scale, 'info>(
+        self,
+        _value: u64,
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Err(DecodeError::from_string("visit_compact_u64 not implemented").into())
+    }
+    /// Called when a compact-encoded u128 is seen in the input bytes.
+    fn visit_compact_u128<'scale, 'info>(
+        self,
+        _value: u128,
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Err(DecodeError::from_string("visit_compact_u128 not implemented").into())
+    }
+    /// Called when a string is seen in the input bytes.
+    fn visit_str<'scale, 'info>(
+        self,
+        _value: &Str<'scale>,
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Err(DecodeError::from_string("visit_str not implemented").into())
+    }
+    /// Called when a sequence of values is seen in the input bytes.
+    fn visit_sequence<'scale, 'info>(
+        self,
+        _value: &mut Sequence<'scale, 'info, Self::TypeResolver>,
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Err(DecodeError::from_string("visit_sequence not implemented").into())
+    }
+    /// Called when a composite value is seen in the input bytes.
+    fn visit_composite<'scale, 'info>(
+        self,
+        _value: &mut Composite<'scale, 'info, Self::TypeResolver>,
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Err(DecodeError::from_string("visit_composite not implemented").into())
+    }
+    /// Called when a tuple is seen in the input bytes.
+    fn visit_tuple<'scale, 'info>(
+        self,
+        _value: &mut Tuple<'scale, 'info, Self::TypeResolver>,
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Err(DecodeError::from_string("visit_tuple not implemented").into())
+    }
+    /// Called when a variant is seen in the input bytes.
+    fn visit_variant<'scale, 'info>(
+        self,
+        _value: &mut Variant<'scale, 'info, Self::TypeResolver>,
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Err(DecodeError::from_string("visit_variant not implemented").into())
+    }
+    /// Called when a fixed length array is seen in the input bytes.
+    fn visit_array<'scale, 'info>(
+        self,
+        _value: &mut Array<'scale, 'info, Self::TypeResolver>,
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Err(DecodeError::from_string("visit_array not implemented").into())
+    }
+    /// Called when a bit sequence is seen in the input bytes.
+    fn visit_bitsequence<'scale, 'info>(
+        self,
+        _value: &mut BitSequence<'scale>,
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Err(DecodeError::from_string("visit_bitsequence not implemented").into())
+    }
+    /// Called instead of [`Self::visit_sequence`] or [`Self::visit_array`] when the element type
+    /// is known to be `u8`, allowing the whole run of bytes to be borrowed in one go rather than
+    /// visited one item at a time. The default implementation returns `None`, in which case
+    /// `decode_with_visitor` falls back to [`Self::visit_sequence`]/[`Self::visit_array`], so
+    /// implementing this is purely an opt-in optimisation.
+    fn visit_u8_slice<'scale, 'info>(
+        self,
+        _bytes: &'scale [u8],
+    ) -> Option<Result<Self::Value<'scale, 'info>, Self::Error>> {
+        None
+    }
+}
+
+/// This is implemented for iterator-like types (eg [`Composite`], [`Tuple`], [`Variant`] and
+/// [`Sequence`]) which decode one item at a time given a [`Visitor`] to hand each item to.
+pub trait DecodeItemIterator<'scale, 'info> {
+    /// The resolver used to look up type information while decoding.
+    type TypeResolver: TypeResolver;
+    /// Decode the next item in the sequence using the given visitor.
+    fn decode_item<'a, V: Visitor<TypeResolver = Self::TypeResolver>>(
+        &mut self,
+        visitor: V,
+    ) -> Option<Result<V::Value<'scale, 'info>, V::Error>>;
+}
+
+/// A [`Visitor`] implementation which ignores all of the bytes, useful for skipping over values
+/// we don't care about decoding.
+pub struct IgnoreVisitor<R = PortableRegistry>(core::marker::PhantomData<fn() -> R>);
+
+impl<R> Default for IgnoreVisitor<R> {
+    fn default() -> Self {
+        IgnoreVisitor(core::marker::PhantomData)
+    }
+}
+
+impl<R: TypeResolver> Visitor for IgnoreVisitor<R> {
+    type TypeResolver = R;
+    type Value<'scale, 'info> = ();
+    type Error = DecodeError;
+
+    fn visit_bool<'scale, 'info>(self, _value: bool) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Ok(())
+    }
+    fn visit_char<'scale, 'info>(self, _value: char) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Ok(())
+    }
+    fn visit_u8<'scale, 'info>(self, _value: u8) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Ok(())
+    }
+    fn visit_u16<'scale, 'info>(self, _value: u16) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Ok(())
+    }
+    fn visit_u32<'scale, 'info>(self, _value: u32) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Ok(())
+    }
+    fn visit_u64<'scale, 'info>(self, _value: u64) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Ok(())
+    }
+    fn visit_u128<'scale, 'info>(self, _value: u128) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Ok(())
+    }
+    fn visit_u256<'scale, 'info>(
+        self,
+        _value: &'scale [u8; 32],
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Ok(())
+    }
+    fn visit_i8<'scale, 'info>(self, _value: i8) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Ok(())
+    }
+    fn visit_i16<'scale, 'info>(self, _value: i16) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Ok(())
+    }
+    fn visit_i32<'scale, 'info>(self, _value: i32) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Ok(())
+    }
+    fn visit_i64<'scale, 'info>(self, _value: i64) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Ok(())
+    }
+    fn visit_i128<'scale, 'info>(self, _value: i128) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Ok(())
+    }
+    fn visit_i256<'scale, 'info>(
+        self,
+        _value: &'scale [u8; 32],
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Ok(())
+    }
+    fn visit_compact_u8<'scale, 'info>(
+        self,
+        _value: u8,
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Ok(())
+    }
+    fn visit_compact_u16<'scale, 'info>(
+        self,
+        _value: u16,
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Ok(())
+    }
+    fn visit_compact_u32<'scale, 'info>(
+        self,
+        _value: u32,
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Ok(())
+    }
+    fn visit_compact_u64<'scale, 'info>(
+        self,
+        _value: u64,
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Ok(())
+    }
+    fn visit_compact_u128<'scale, 'info>(
+        self,
+        _value: u128,
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Ok(())
+    }
+    fn visit_str<'scale, 'info>(
+        self,
+        _value: &Str<'scale>,
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        Ok(())
+    }
+    fn visit_sequence<'scale, 'info>(
+        self,
+        value: &mut Sequence<'scale, 'info, Self::TypeResolver>,
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        value.skip_decoding()
+    }
+    fn visit_composite<'scale, 'info>(
+        self,
+        value: &mut Composite<'scale, 'info, Self::TypeResolver>,
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        value.skip_decoding()
+    }
+    fn visit_tuple<'scale, 'info>(
+        self,
+        value: &mut Tuple<'scale, 'info, Self::TypeResolver>,
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        value.skip_decoding()
+    }
+    fn visit_variant<'scale, 'info>(
+        self,
+        value: &mut Variant<'scale, 'info, Self::TypeResolver>,
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        value.skip_decoding()
+    }
+    fn visit_array<'scale, 'info>(
+        self,
+        value: &mut Array<'scale, 'info, Self::TypeResolver>,
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        value.skip_decoding()
+    }
+    fn visit_bitsequence<'scale, 'info>(
+        self,
+        value: &mut BitSequence<'scale>,
+    ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+        value.decode_bitsequence().map(|_| ())
+    }
+    fn visit_u8_slice<'scale, 'info>(
+        self,
+        _bytes: &'scale [u8],
+    ) -> Option<Result<Self::Value<'scale, 'info>, Self::Error>> {
+        Some(Ok(()))
+    }
+}
+
+/// Whether the given primitive is `u8`; used by [`decode_with_visitor`] when deciding whether the
+/// element type of an [`Array`]/[`Sequence`] is `u8` and a fast-path borrow can be used.
+pub(crate) fn is_u8_primitive(primitive: &TypeDefPrimitive) -> bool {
+    matches!(primitive, TypeDefPrimitive::U8)
+}
+
+/// Decode some SCALE encoded bytes into a value by providing a visitor to handle each possible
+/// shape that the type (looked up via `type_id` in `types`) might have.
+pub fn decode_with_visitor<'scale, 'info, R, V>(
+    input: &mut &'scale [u8],
+    type_id: R::TypeId,
+    types: &'info R,
+    visitor: V,
+) -> Result<V::Value<'scale, 'info>, V::Error>
+where
+    R: TypeResolver,
+    V: Visitor<TypeResolver = R>,
+{
+    use crate::type_resolver::{FieldInfo, ResolvedTypeVisitor, VariantInfo};
+
+    /// Figure out whether a given type ID resolves to the `u8` primitive, so that `Sequence`s
+    /// and `Array`s of it can take the zero-copy fast path rather than visiting item-by-item.
+    fn resolves_to_u8<R: TypeResolver>(id: &R::TypeId, types: &R) -> bool {
+        struct IsU8;
+        impl<Id> ResolvedTypeVisitor<Id> for IsU8 {
+            type Value = bool;
+            fn visit_primitive(self, primitive: TypeDefPrimitive) -> bool {
+                is_u8_primitive(&primitive)
+            }
+            fn visit_unknown(self) -> bool {
+                false
+            }
+        }
+        types.resolve(id, IsU8).unwrap_or(false)
+    }
+
+    struct Dispatch<'i, 'scale, 'info, R: TypeResolver, V> {
+        input: &'i mut &'scale [u8],
+        types: &'info R,
+        visitor: V,
+    }
+
+    impl<'i, 'scale, 'info, R: TypeResolver, V: Visitor<TypeResolver = R>> ResolvedTypeVisitor<R::TypeId>
+        for Dispatch<'i, 'scale, 'info, R, V>
+    {
+        type Value = Result<V::Value<'scale, 'info>, V::Error>;
+
+        fn visit_composite(self, fields: &[FieldInfo<R::TypeId>]) -> Self::Value {
+            let owned_fields = fields.iter().cloned().collect();
+            let mut composite = Composite::new(*self.input, owned_fields, self.types);
+            let res = self.visitor.visit_composite(&mut composite);
+            *self.input = composite.bytes_from_undecoded();
+            res
+        }
+        fn visit_variant(self, variants: &[VariantInfo<R::TypeId>]) -> Self::Value {
+            let bytes_from_start = *self.input;
+            let index: u8 = match codec::Decode::decode(self.input) {
+                Ok(v) => v,
+                Err(e) => {
+                    return Err(DecodeError::from_string(alloc::format!(
+                        "Cannot decode variant index: {e}"
+                    ))
+                    .into())
+                }
+            };
+            let Some(variant) = variants.iter().find(|v| v.index == index) else {
+                return Err(DecodeError::from_string(alloc::format!(
+                    "No variant with index {index} found"
+                ))
+                .into());
+            };
+
+            let fields = variant.fields.clone();
+            let composite = Composite::new(*self.input, fields, self.types);
+            let mut var = Variant::new(bytes_from_start, variant.clone(), composite);
+            let res = self.visitor.visit_variant(&mut var);
+            *self.input = var.fields().bytes_from_undecoded();
+            res
+        }
+        fn visit_sequence(self, inner: R::TypeId) -> Self::Value {
+            let is_u8 = resolves_to_u8(&inner, self.types);
+            let (len, rest) = match decode_compact_len(self.input) {
+                Ok(v) => v,
+                Err(e) => return Err(e.into()),
+            };
+            let mut seq = Sequence::new(rest, len, inner, self.types, is_u8);
+
+            if let Some(bytes) = seq.as_u8_slice() {
+                if let Some(res) = self.visitor.visit_u8_slice(bytes) {
+                    *self.input = &rest[len..];
+                    return res;
+                }
+            }
+
+            let res = self.visitor.visit_sequence(&mut seq);
+            *self.input = seq.bytes_from_undecoded();
+            res
+        }
+        fn visit_array(self, inner: R::TypeId, len: usize) -> Self::Value {
+            let is_u8 = resolves_to_u8(&inner, self.types);
+            let mut arr = Array::new(*self.input, len, inner, self.types, is_u8);
+
+            if let Some(bytes) = arr.as_u8_slice() {
+                if let Some(res) = self.visitor.visit_u8_slice(bytes) {
+                    *self.input = &arr.bytes_from_undecoded()[len..];
+                    return res;
+                }
+            }
+
+            let res = self.visitor.visit_array(&mut arr);
+            *self.input = arr.bytes_from_undecoded();
+            res
+        }
+        fn visit_tuple(self, type_ids: &[R::TypeId]) -> Self::Value {
+            let owned_ids = type_ids.iter().cloned().collect();
+            let mut tuple = Tuple::new(*self.input, owned_ids, self.types);
+            let res = self.visitor.visit_tuple(&mut tuple);
+            *self.input = tuple.bytes_from_undecoded();
+            res
+        }
+        fn visit_primitive(self, primitive: TypeDefPrimitive) -> Self::Value {
+            decode_primitive(self.input, primitive, self.visitor)
+        }
+        fn visit_compact(self, inner: R::TypeId) -> Self::Value {
+            let primitive = match resolve_primitive(&inner, self.types) {
+                Some(p) => p,
+                None => {
+                    return Err(DecodeError::from_string(
+                        "Compact encoding is only supported for unsigned integer primitives",
+                    )
+                    .into())
+                }
+            };
+            decode_compact(self.input, primitive, self.visitor)
+        }
+        fn visit_bit_sequence(
+            self,
+            store: TypeDefPrimitive,
+            order: crate::type_resolver::BitOrder,
+        ) -> Self::Value {
+            let format = match build_bit_sequence_format(store, order) {
+                Ok(f) => f,
+                Err(e) => return Err(e.into()),
+            };
+            let mut bits = BitSequence::new(*self.input, format);
+            let res = self.visitor.visit_bitsequence(&mut bits);
+            *self.input = bits.bytes_from_undecoded();
+            res
+        }
+        fn visit_unknown(self) -> Self::Value {
+            Err(DecodeError::from_string("Could not resolve the type being decoded").into())
+        }
+    }
+
+    /// Build the `scale_bits` [`Format`] that describes how to decode a bit sequence, given the
+    /// store primitive and bit ordering that [`crate::type_resolver::TypeResolver::resolve`]
+    /// worked out for it.
+    fn build_bit_sequence_format(
+        store: TypeDefPrimitive,
+        order: crate::type_resolver::BitOrder,
+    ) -> Result<scale_bits::scale::format::Format, DecodeError> {
+        use crate::type_resolver::BitOrder;
+        use scale_bits::scale::format::{OrderFormat, StoreFormat};
+
+        let store = match store {
+            TypeDefPrimitive::U8 => StoreFormat::U8,
+            TypeDefPrimitive::U16 => StoreFormat::U16,
+            TypeDefPrimitive::U32 => StoreFormat::U32,
+            TypeDefPrimitive::U64 => StoreFormat::U64,
+            other => {
+                return Err(DecodeError::from_string(alloc::format!(
+                    "Bit sequences cannot be stored in a {other:?}"
+                )))
+            }
+        };
+        let order = match order {
+            BitOrder::Lsb0 => OrderFormat::Lsb0,
+            BitOrder::Msb0 => OrderFormat::Msb0,
+        };
+
+        Ok(scale_bits::scale::format::Format { store, order })
+    }
+
+    fn decode_compact_len<'scale>(
+        input: &mut &'scale [u8],
+    ) -> Result<(usize, &'scale [u8]), DecodeError> {
+        let len = <codec::Compact<u32> as codec::Decode>::decode(input)
+            .map_err(|e| DecodeError::from_string(alloc::format!("Cannot decode length: {e}")))?
+            .0 as usize;
+        Ok((len, input))
+    }
+
+    /// Look up the `TypeDefPrimitive` that a compact type's inner type ID resolves to, if it
+    /// resolves to any primitive at all; `decode_compact` rejects the non-unsigned-integer ones.
+    fn resolve_primitive<R: TypeResolver>(id: &R::TypeId, types: &R) -> Option<TypeDefPrimitive> {
+        struct AsPrimitive;
+        impl<Id> ResolvedTypeVisitor<Id> for AsPrimitive {
+            type Value = Option<TypeDefPrimitive>;
+            fn visit_primitive(self, primitive: TypeDefPrimitive) -> Self::Value {
+                Some(primitive)
+            }
+            fn visit_unknown(self) -> Self::Value {
+                None
+            }
+        }
+        types.resolve(id, AsPrimitive).ok().flatten()
+    }
+
+    macro_rules! decode_num {
+        ($input:ident, $ty:ty, $visit:ident, $visitor:ident) => {{
+            let v: $ty = codec::Decode::decode($input)
+                .map_err(|e| DecodeError::from_string(alloc::format!("{e}")))?;
+            $visitor.$visit(v)
+        }};
+    }
+
+    fn decode_fixed_bytes<'scale>(
+        input: &mut &'scale [u8],
+    ) -> Result<&'scale [u8; 32], DecodeError> {
+        if input.len() < 32 {
+            return Err(DecodeError::from_string("Not enough bytes to decode a 256 bit value"));
+        }
+        let (value, rest) = input.split_at(32);
+        *input = rest;
+        Ok(value.try_into().expect("checked length above"))
+    }
+
+    macro_rules! decode_compact_num {
+        ($input:ident, $ty:ty, $visit:ident, $visitor:ident) => {{
+            let v: codec::Compact<$ty> = codec::Decode::decode($input)
+                .map_err(|e| DecodeError::from_string(alloc::format!("{e}")))?;
+            $visitor.$visit(v.0)
+        }};
+    }
+
+    fn decode_compact<'scale, 'info, V: Visitor>(
+        input: &mut &'scale [u8],
+        primitive: TypeDefPrimitive,
+        visitor: V,
+    ) -> Result<V::Value<'scale, 'info>, V::Error> {
+        match primitive {
+            TypeDefPrimitive::U8 => decode_compact_num!(input, u8, visit_compact_u8, visitor),
+            TypeDefPrimitive::U16 => decode_compact_num!(input, u16, visit_compact_u16, visitor),
+            TypeDefPrimitive::U32 => decode_compact_num!(input, u32, visit_compact_u32, visitor),
+            TypeDefPrimitive::U64 => decode_compact_num!(input, u64, visit_compact_u64, visitor),
+            TypeDefPrimitive::U128 => decode_compact_num!(input, u128, visit_compact_u128, visitor),
+            other => Err(DecodeError::from_string(alloc::format!(
+                "Compact encoding of {other:?} is not supported"
+            ))
+            .into()),
+        }
+    }
+
+    fn decode_primitive<'scale, 'info, V: Visitor>(
+        input: &mut &'scale [u8],
+        primitive: TypeDefPrimitive,
+        visitor: V,
+    ) -> Result<V::Value<'scale, 'info>, V::Error> {
+        match primitive {
+            TypeDefPrimitive::Bool => decode_num!(input, bool, visit_bool, visitor),
+            TypeDefPrimitive::Char => decode_num!(input, char, visit_char, visitor),
+            TypeDefPrimitive::U8 => decode_num!(input, u8, visit_u8, visitor),
+            TypeDefPrimitive::U16 => decode_num!(input, u16, visit_u16, visitor),
+            TypeDefPrimitive::U32 => decode_num!(input, u32, visit_u32, visitor),
+            TypeDefPrimitive::U64 => decode_num!(input, u64, visit_u64, visitor),
+            TypeDefPrimitive::U128 => decode_num!(input, u128, visit_u128, visitor),
+            TypeDefPrimitive::I8 => decode_num!(input, i8, visit_i8, visitor),
+            TypeDefPrimitive::I16 => decode_num!(input, i16, visit_i16, visitor),
+            TypeDefPrimitive::I32 => decode_num!(input, i32, visit_i32, visitor),
+            TypeDefPrimitive::I64 => decode_num!(input, i64, visit_i64, visitor),
+            TypeDefPrimitive::I128 => decode_num!(input, i128, visit_i128, visitor),
+            TypeDefPrimitive::U256 => {
+                let bytes = decode_fixed_bytes(input)?;
+                visitor.visit_u256(bytes)
+            }
+            TypeDefPrimitive::I256 => {
+                let bytes = decode_fixed_bytes(input)?;
+                visitor.visit_i256(bytes)
+            }
+            TypeDefPrimitive::Str => {
+                // Decode into a throwaway owned `String` purely to work out how many bytes the
+                // string takes up, then hand the visitor a `Str` borrowing the original bytes
+                // rather than the copy.
+                let start = *input;
+                let s: alloc::string::String = codec::Decode::decode(input)
+                    .map_err(|e| DecodeError::from_string(alloc::format!("{e}")))?;
+                let _ = s;
+                let consumed = start.len() - input.len();
+                let str_bytes = &start[..consumed];
+                let value = Str::new(str_bytes);
+                visitor.visit_str(&value)
+            }
+        }
+    }
+
+    types
+        .resolve(&type_id, Dispatch { input, types, visitor })
+        .unwrap_or_else(|e| Err(DecodeError::from_string(alloc::format!("{e:?}")).into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::type_resolver::ResolvedTypeVisitor;
+    use codec::Encode;
+
+    /// A `TypeResolver` whose one and only type ID (`0`) always resolves to the given primitive;
+    /// just enough to exercise `decode_with_visitor`'s primitive dispatch without needing a
+    /// full `scale_info::PortableRegistry`.
+    struct SinglePrimitiveResolver(TypeDefPrimitive);
+
+    impl TypeResolver for SinglePrimitiveResolver {
+        type TypeId = u32;
+        type Error = DecodeError;
+
+        fn resolve<V: ResolvedTypeVisitor<Self::TypeId>>(
+            &self,
+            _type_id: &Self::TypeId,
+            visitor: V,
+        ) -> Result<V::Value, Self::Error> {
+            Ok(visitor.visit_primitive(self.0.clone()))
+        }
+    }
+
+    struct JustU32;
+    impl Visitor for JustU32 {
+        type TypeResolver = SinglePrimitiveResolver;
+        type Value<'scale, 'info> = u32;
+        type Error = DecodeError;
+
+        fn visit_u32<'scale, 'info>(
+            self,
+            value: u32,
+        ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+            Ok(value)
+        }
+    }
+
+    #[test]
+    fn decodes_u32_primitive() {
+        let types = SinglePrimitiveResolver(TypeDefPrimitive::U32);
+        let bytes = 123u32.to_le_bytes();
+        let mut input = &bytes[..];
+        let value = decode_with_visitor(&mut input, 0, &types, JustU32).unwrap();
+        assert_eq!(value, 123);
+        assert!(input.is_empty());
+    }
+
+    struct CollectU8Slice;
+    impl Visitor for CollectU8Slice {
+        type TypeResolver = SequenceOfU8Resolver;
+        type Value<'scale, 'info> = alloc::vec::Vec<u8>;
+        type Error = DecodeError;
+
+        fn visit_u8_slice<'scale, 'info>(
+            self,
+            bytes: &'scale [u8],
+        ) -> Option<Result<Self::Value<'scale, 'info>, Self::Error>> {
+            Some(Ok(bytes.to_vec()))
+        }
+    }
+
+    /// Type ID `0` is a sequence of type ID `1`, which is itself the `u8` primitive.
+    struct SequenceOfU8Resolver;
+    impl TypeResolver for SequenceOfU8Resolver {
+        type TypeId = u32;
+        type Error = DecodeError;
+        fn resolve<V: ResolvedTypeVisitor<Self::TypeId>>(
+            &self,
+            type_id: &Self::TypeId,
+            visitor: V,
+        ) -> Result<V::Value, Self::Error> {
+            match type_id {
+                0 => Ok(visitor.visit_sequence(1)),
+                _ => Ok(visitor.visit_primitive(TypeDefPrimitive::U8)),
+            }
+        }
+    }
+
+    #[test]
+    fn sequence_of_u8_takes_the_fast_path() {
+        // Compact-encoded length of 3, followed by the 3 bytes themselves.
+        let mut bytes = codec::Compact(3u32).encode();
+        bytes.extend_from_slice(&[1, 2, 3]);
+        let mut input = &bytes[..];
+
+        let value =
+            decode_with_visitor(&mut input, 0, &SequenceOfU8Resolver, CollectU8Slice).unwrap();
+        assert_eq!(value, alloc::vec![1, 2, 3]);
+        assert!(input.is_empty());
+    }
+
+    /// Type ID `0` always resolves to a `u8`-stored, `Lsb0`-ordered bit sequence.
+    struct U8Lsb0BitSequenceResolver;
+    impl TypeResolver for U8Lsb0BitSequenceResolver {
+        type TypeId = u32;
+        type Error = DecodeError;
+        fn resolve<V: ResolvedTypeVisitor<Self::TypeId>>(
+            &self,
+            _type_id: &Self::TypeId,
+            visitor: V,
+        ) -> Result<V::Value, Self::Error> {
+            Ok(visitor.visit_bit_sequence(TypeDefPrimitive::U8, crate::type_resolver::BitOrder::Lsb0))
+        }
+    }
+
+    struct DecodeBits;
+    impl Visitor for DecodeBits {
+        type TypeResolver = U8Lsb0BitSequenceResolver;
+        type Value<'scale, 'info> = scale_bits::Bits;
+        type Error = DecodeError;
+
+        fn visit_bitsequence<'scale, 'info>(
+            self,
+            value: &mut types::BitSequence<'scale>,
+        ) -> Result<Self::Value<'scale, 'info>, Self::Error> {
+            value.decode_bitsequence()
+        }
+    }
+
+    #[test]
+    fn bit_sequence_round_trips() {
+        use scale_bits::scale::format::{Format, OrderFormat, StoreFormat};
+
+        let format = Format { store: StoreFormat::U8, order: OrderFormat::Lsb0 };
+        let bits: scale_bits::Bits = [true, false, true].into_iter().collect();
+
+        let mut bytes = alloc::vec::Vec::new();
+        scale_bits::encode_using_format_to(bits.iter(), format, &mut bytes);
+        let mut input = &bytes[..];
+
+        let decoded =
+            decode_with_visitor(&mut input, 0, &U8Lsb0BitSequenceResolver, DecodeBits).unwrap();
+        assert_eq!(decoded.iter().collect::<alloc::vec::Vec<_>>(), alloc::vec![true, false, true]);
+        assert!(input.is_empty());
+    }
+}
@@ -0,0 +1,54 @@
+// Copyright (C) 2023 Parity Technologies (UK) Ltd. (admin@parity.io)
+// This file is a part of the scale-decode crate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//         http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::visitor::DecodeError;
+use alloc::string::ToString;
+use scale_bits::{scale::format::Format, Bits};
+
+/// A bit sequence, decoded via [`scale_bits`]. This holds only the decoded bits: the store and
+/// order type information is carried by [`BitSequence`] and used while decoding, and isn't kept
+/// in the value itself, so re-encoding it needs that format to be supplied again.
+pub type BitSequenceValue = Bits;
+
+/// This represents a SCALE encoded bit sequence, and exposes a way to decode this into a
+/// [`BitSequenceValue`].
+pub struct BitSequence<'scale> {
+    bytes: &'scale [u8],
+    format: Format,
+}
+
+impl<'scale> BitSequence<'scale> {
+    // Used by `decode_with_visitor`, but not really expected to be used elsewhere.
+    #[doc(hidden)]
+    pub fn new(bytes: &'scale [u8], format: Format) -> BitSequence<'scale> {
+        BitSequence { bytes, format }
+    }
+    /// Decode the bit sequence into a [`BitSequenceValue`]. The store/order types recorded
+    /// against the original type inform how the underlying bytes are interpreted, so the
+    /// result round-trips identically for the `u8`/`u16`/`u32` store and `Lsb0`/`Msb0` order
+    /// combinations that `scale-info` can describe.
+    pub fn decode_bitsequence(&mut self) -> Result<BitSequenceValue, DecodeError> {
+        let mut cursor = self.bytes;
+        let bits = scale_bits::decode_using_format_from(&mut cursor, self.format)
+            .map_err(|e| DecodeError::from_string(e.to_string()))?;
+        self.bytes = cursor;
+        Ok(bits)
+    }
+    /// The bytes that have not yet been decoded. Before [`Self::decode_bitsequence`] has been
+    /// called, this is the same as the bytes the bit sequence was constructed with.
+    pub fn bytes_from_undecoded(&self) -> &'scale [u8] {
+        self.bytes
+    }
+}
@@ -14,36 +14,37 @@
 // limitations under the License.
 
 use crate::{
+    type_resolver::FieldInfo,
     visitor::{DecodeError, IgnoreVisitor, Visitor},
-    DecodeAsType,
+    DecodeAsType, TypeResolver,
 };
-use scale_info::{form::PortableForm, Field, Path, PortableRegistry};
+use alloc::vec::Vec;
+use scale_info::PortableRegistry;
 
 /// This represents a composite type.
-pub struct Composite<'scale, 'info> {
+pub struct Composite<'scale, 'info, R: TypeResolver = PortableRegistry> {
     bytes: &'scale [u8],
     item_bytes: &'scale [u8],
-    path: &'info Path<PortableForm>,
-    fields: &'info [Field<PortableForm>],
-    types: &'info PortableRegistry,
+    fields: Vec<FieldInfo<R::TypeId>>,
+    position: usize,
+    types: &'info R,
 }
 
-impl<'scale, 'info> Composite<'scale, 'info> {
+impl<'scale, 'info, R: TypeResolver> Composite<'scale, 'info, R> {
     // Used in macros, but not really expected to be used elsewhere.
     #[doc(hidden)]
     pub fn new(
         bytes: &'scale [u8],
-        path: &'info Path<PortableForm>,
-        fields: &'info [Field<PortableForm>],
-        types: &'info PortableRegistry,
-    ) -> Composite<'scale, 'info> {
-        Composite { bytes, path, item_bytes: bytes, fields, types }
+        fields: Vec<FieldInfo<R::TypeId>>,
+        types: &'info R,
+    ) -> Composite<'scale, 'info, R> {
+        Composite { bytes, item_bytes: bytes, fields, position: 0, types }
     }
     /// Skip over all bytes associated with this composite type. After calling this,
     /// [`Self::bytes_from_undecoded()`] will represent the bytes after this composite type.
     pub fn skip_decoding(&mut self) -> Result<(), DecodeError> {
-        while !self.fields.is_empty() {
-            self.decode_item(IgnoreVisitor).transpose()?;
+        while self.position < self.fields.len() {
+            self.decode_item(IgnoreVisitor::default()).transpose()?;
         }
         Ok(())
     }
@@ -58,66 +59,102 @@ impl<'scale, 'info> Composite<'scale, 'info> {
     }
     /// The number of un-decoded items remaining in this composite type.
     pub fn remaining(&self) -> usize {
-        self.fields.len()
-    }
-    /// Path to this type.
-    pub fn path(&self) -> &'info Path<PortableForm> {
-        self.path
+        self.fields.len() - self.position
     }
     /// The yet-to-be-decoded fields still present in this composite type.
-    pub fn fields(&self) -> &'info [Field<PortableForm>] {
-        self.fields
+    pub fn fields(&self) -> &[FieldInfo<R::TypeId>] {
+        &self.fields[self.position..]
     }
     /// Return whether any of the fields are unnamed.
     pub fn has_unnamed_fields(&self) -> bool {
-        self.fields.iter().any(|f| f.name().is_none())
+        self.fields().iter().any(|f| f.name.is_none())
     }
     /// Convert the remaining fields in this Composite type into a [`super::Tuple`]. This allows them to
     /// be parsed in the same way as a tuple type, discarding name information.
-    pub fn as_tuple(&self) -> super::Tuple<'scale, 'info> {
-        super::Tuple::new(self.item_bytes, self.fields, self.types)
+    pub fn as_tuple(&self) -> super::Tuple<'scale, 'info, R> {
+        let ids = self.fields().iter().map(|f| f.id.clone()).collect();
+        super::Tuple::new(self.item_bytes, ids, self.types)
     }
     /// Return the name of the next field to be decoded; `None` if either the field has no name,
     /// or there are no fields remaining.
-    pub fn peek_name(&self) -> Option<&'info str> {
-        self.fields.get(0).and_then(|f| f.name().map(|n| &**n))
+    pub fn peek_name(&self) -> Option<&str> {
+        self.fields.get(self.position).and_then(|f| f.name.as_deref())
+    }
+    /// Find and return the field with a matching name in what remains of this composite type,
+    /// without consuming any of the fields before it. Any intervening fields are skipped (and
+    /// discarded) using [`IgnoreVisitor`] purely to work out how many bytes they occupy; this is
+    /// cheaper than fully decoding-and-discarding them would be. Returns `None` if no remaining
+    /// field has this name, or if skipping over some intervening field fails to decode.
+    pub fn find_field(&self, name: &str) -> Option<CompositeField<'scale, 'info, R>> {
+        self.fields_by_name().find(|f| f.name() == Some(name))
+    }
+    /// Iterate over the fields that remain in this composite type by name, without consuming
+    /// them in order; each field's bytes are computed by skipping over (and discarding) anything
+    /// that comes before it using [`IgnoreVisitor`]. This is what a derived `DecodeAsType` impl
+    /// uses to match SCALE field names against Rust field names, irrespective of field order.
+    pub fn fields_by_name(&self) -> impl Iterator<Item = CompositeField<'scale, 'info, R>> + '_ {
+        let types = self.types;
+        let mut bytes = self.item_bytes;
+        let mut idx = self.position;
+
+        core::iter::from_fn(move || {
+            let field = self.fields.get(idx)?.clone();
+            let name = field.name.clone();
+            let item_bytes = bytes;
+            let num_bytes_before = bytes.len();
+
+            // Use `IgnoreVisitor` purely to work out how many bytes this field takes up,
+            // without fully decoding (and discarding) it into some concrete value.
+            crate::visitor::decode_with_visitor(
+                &mut bytes,
+                field.id.clone(),
+                types,
+                IgnoreVisitor::default(),
+            )
+            .ok()?;
+
+            let num_bytes_after = bytes.len();
+            let field_bytes = &item_bytes[..num_bytes_before - num_bytes_after];
+
+            idx += 1;
+            Some(CompositeField { name, bytes: field_bytes, field, types })
+        })
     }
     /// Decode the next field in the composite type by providing a visitor to handle it. This is more
     /// efficient than iterating over the key/value pairs if you already know how you want to decode the
     /// values.
-    pub fn decode_item<V: Visitor>(
+    pub fn decode_item<V: Visitor<TypeResolver = R>>(
         &mut self,
         visitor: V,
     ) -> Option<Result<V::Value<'scale, 'info>, V::Error>> {
-        if self.fields.is_empty() {
+        if self.position >= self.fields.len() {
             return None;
         }
 
-        let field = &self.fields[0];
+        let field = &self.fields[self.position];
         let b = &mut &*self.item_bytes;
 
         // Decode the bytes:
-        let res = crate::visitor::decode_with_visitor(b, field.ty().id(), self.types, visitor);
+        let res = crate::visitor::decode_with_visitor(b, field.id.clone(), self.types, visitor);
 
         // Update self to point to the next item, now:
         self.item_bytes = *b;
-        self.fields = &self.fields[1..];
+        self.position += 1;
 
         Some(res)
     }
 }
 
 // Iterating returns a representation of each field in the composite type.
-impl<'scale, 'info> Iterator for Composite<'scale, 'info> {
-    type Item = Result<CompositeField<'scale, 'info>, DecodeError>;
+impl<'scale, 'info, R: TypeResolver> Iterator for Composite<'scale, 'info, R> {
+    type Item = Result<CompositeField<'scale, 'info, R>, DecodeError>;
     fn next(&mut self) -> Option<Self::Item> {
         // Record details we need before we decode and skip over the thing:
-        let field = self.fields.get(0)?;
-        let name = self.peek_name();
+        let field = self.fields.get(self.position)?.clone();
         let num_bytes_before = self.item_bytes.len();
         let item_bytes = self.item_bytes;
 
-        if let Err(e) = self.decode_item(IgnoreVisitor)? {
+        if let Err(e) = self.decode_item(IgnoreVisitor::default())? {
             return Some(Err(e));
         };
 
@@ -125,55 +162,135 @@ impl<'scale, 'info> Iterator for Composite<'scale, 'info> {
         let num_bytes_after = self.item_bytes.len();
         let res_bytes = &item_bytes[..num_bytes_before - num_bytes_after];
 
+        let name = field.name.clone();
         Some(Ok(CompositeField { bytes: res_bytes, field, name, types: self.types }))
     }
 }
 
 /// A single field in the composite type.
-#[derive(Copy, Clone)]
-pub struct CompositeField<'scale, 'info> {
-    name: Option<&'info str>,
+pub struct CompositeField<'scale, 'info, R: TypeResolver = PortableRegistry> {
+    name: Option<alloc::string::String>,
     bytes: &'scale [u8],
-    field: &'info Field<PortableForm>,
-    types: &'info PortableRegistry,
+    field: FieldInfo<R::TypeId>,
+    types: &'info R,
 }
 
-impl<'scale, 'info> CompositeField<'scale, 'info> {
+impl<'scale, 'info, R: TypeResolver> Clone for CompositeField<'scale, 'info, R> {
+    fn clone(&self) -> Self {
+        CompositeField {
+            name: self.name.clone(),
+            bytes: self.bytes,
+            field: self.field.clone(),
+            types: self.types,
+        }
+    }
+}
+
+impl<'scale, 'info, R: TypeResolver> CompositeField<'scale, 'info, R> {
     /// The field name.
-    pub fn name(&self) -> Option<&'info str> {
-        self.name
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
     }
     /// The bytes associated with this field.
     pub fn bytes(&self) -> &'scale [u8] {
         self.bytes
     }
     /// The type ID associated with this field.
-    pub fn type_id(&self) -> u32 {
-        self.field.ty().id()
+    pub fn type_id(&self) -> R::TypeId {
+        self.field.id.clone()
     }
     /// Decode this field using a visitor.
-    pub fn decode_with_visitor<V: Visitor>(
+    pub fn decode_with_visitor<V: Visitor<TypeResolver = R>>(
         &self,
         visitor: V,
     ) -> Result<V::Value<'scale, 'info>, V::Error> {
         crate::visitor::decode_with_visitor(
             &mut &*self.bytes,
-            self.field.ty().id(),
+            self.field.id.clone(),
             self.types,
             visitor,
         )
     }
     /// Decode this field into a specific type via [`DecodeAsType`].
     pub fn decode_as_type<T: DecodeAsType>(&self) -> Result<T, crate::Error> {
-        T::decode_as_type(&mut &*self.bytes, self.field.ty().id(), self.types)
+        T::decode_as_type(&mut &*self.bytes, self.field.id.clone(), self.types)
     }
 }
 
-impl<'scale, 'info> crate::visitor::DecodeItemIterator<'scale, 'info> for Composite<'scale, 'info> {
-    fn decode_item<'a, V: Visitor>(
+impl<'scale, 'info, R: TypeResolver> crate::visitor::DecodeItemIterator<'scale, 'info>
+    for Composite<'scale, 'info, R>
+{
+    type TypeResolver = R;
+    fn decode_item<'a, V: Visitor<TypeResolver = R>>(
         &mut self,
         visitor: V,
     ) -> Option<Result<V::Value<'scale, 'info>, V::Error>> {
         self.decode_item(visitor)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::type_resolver::ResolvedTypeVisitor;
+    use scale_info::TypeDefPrimitive;
+
+    /// Every type ID in this resolver resolves to the `u32` primitive; just enough to build and
+    /// decode a [`Composite`] without needing a full `scale_info::PortableRegistry`.
+    struct AllU32Resolver;
+    impl TypeResolver for AllU32Resolver {
+        type TypeId = u32;
+        type Error = crate::visitor::DecodeError;
+        fn resolve<V: ResolvedTypeVisitor<Self::TypeId>>(
+            &self,
+            _type_id: &Self::TypeId,
+            visitor: V,
+        ) -> Result<V::Value, Self::Error> {
+            Ok(visitor.visit_primitive(TypeDefPrimitive::U32))
+        }
+    }
+
+    fn fields() -> Vec<FieldInfo<u32>> {
+        vec![
+            FieldInfo { name: Some(alloc::string::String::from("a")), id: 0 },
+            FieldInfo { name: Some(alloc::string::String::from("b")), id: 0 },
+        ]
+    }
+
+    fn two_u32_bytes() -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&1u32.to_le_bytes());
+        bytes.extend_from_slice(&2u32.to_le_bytes());
+        bytes
+    }
+
+    #[test]
+    fn find_field_locates_a_later_field_without_consuming_earlier_ones() {
+        let bytes = two_u32_bytes();
+        let types = AllU32Resolver;
+        let composite = Composite::new(&bytes, fields(), &types);
+
+        let b = composite.find_field("b").expect("field 'b' exists");
+        assert_eq!(b.decode_as_type::<u32>().unwrap(), 2);
+
+        // The fields before "b" are untouched, so "a" can still be found afterwards too.
+        let a = composite.find_field("a").expect("field 'a' exists");
+        assert_eq!(a.decode_as_type::<u32>().unwrap(), 1);
+
+        assert!(composite.find_field("c").is_none());
+    }
+
+    #[test]
+    fn fields_by_name_yields_every_remaining_field() {
+        let bytes = two_u32_bytes();
+        let types = AllU32Resolver;
+        let composite = Composite::new(&bytes, fields(), &types);
+
+        let names: Vec<_> =
+            composite.fields_by_name().map(|f| f.name().map(alloc::string::String::from)).collect();
+        assert_eq!(
+            names,
+            vec![Some(alloc::string::String::from("a")), Some(alloc::string::String::from("b"))]
+        );
+    }
+}
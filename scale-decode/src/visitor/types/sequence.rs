@@ -0,0 +1,98 @@
+// Copyright (C) 2023 Parity Technologies (UK) Ltd. (admin@parity.io)
+// This file is a part of the scale-decode crate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//         http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    visitor::{DecodeError, IgnoreVisitor, Visitor},
+    TypeResolver,
+};
+use scale_info::PortableRegistry;
+
+/// This represents a sequence of values.
+pub struct Sequence<'scale, 'info, R: TypeResolver = PortableRegistry> {
+    bytes: &'scale [u8],
+    remaining: usize,
+    type_id: R::TypeId,
+    types: &'info R,
+    // Set when the element type resolves to `u8`, letting us borrow the whole
+    // run of bytes in one go rather than visiting element-by-element.
+    is_u8: bool,
+}
+
+impl<'scale, 'info, R: TypeResolver> Sequence<'scale, 'info, R> {
+    // Used by `decode_with_visitor`, but not really expected to be used elsewhere.
+    #[doc(hidden)]
+    pub fn new(
+        bytes: &'scale [u8],
+        remaining: usize,
+        type_id: R::TypeId,
+        types: &'info R,
+        is_u8: bool,
+    ) -> Sequence<'scale, 'info, R> {
+        Sequence { bytes, remaining, type_id, types, is_u8 }
+    }
+    /// The number of un-decoded items remaining in this sequence.
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+    /// The bytes that have not yet been decoded in this sequence.
+    pub fn bytes_from_undecoded(&self) -> &'scale [u8] {
+        self.bytes
+    }
+    /// If the element type of this sequence is `u8`, borrow the entire remaining run of bytes
+    /// in one go instead of decoding it one item at a time. Returns `None` for any other
+    /// element type, or if there are fewer bytes available than items remaining.
+    pub fn as_u8_slice(&self) -> Option<&'scale [u8]> {
+        if !self.is_u8 || self.bytes.len() < self.remaining {
+            return None;
+        }
+        Some(&self.bytes[..self.remaining])
+    }
+    /// Skip over all of the remaining bytes in this sequence.
+    pub fn skip_decoding(&mut self) -> Result<(), DecodeError> {
+        while self.remaining > 0 {
+            self.decode_item(IgnoreVisitor::default()).transpose()?;
+        }
+        Ok(())
+    }
+    /// Decode the next item in the sequence by providing a visitor to handle it.
+    pub fn decode_item<V: Visitor<TypeResolver = R>>(
+        &mut self,
+        visitor: V,
+    ) -> Option<Result<V::Value<'scale, 'info>, V::Error>> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let b = &mut &*self.bytes;
+        let res = crate::visitor::decode_with_visitor(b, self.type_id.clone(), self.types, visitor);
+
+        self.bytes = *b;
+        self.remaining -= 1;
+
+        Some(res)
+    }
+}
+
+impl<'scale, 'info, R: TypeResolver> crate::visitor::DecodeItemIterator<'scale, 'info>
+    for Sequence<'scale, 'info, R>
+{
+    type TypeResolver = R;
+    fn decode_item<'a, V: Visitor<TypeResolver = R>>(
+        &mut self,
+        visitor: V,
+    ) -> Option<Result<V::Value<'scale, 'info>, V::Error>> {
+        self.decode_item(visitor)
+    }
+}
@@ -0,0 +1,43 @@
+// Copyright (C) 2023 Parity Technologies (UK) Ltd. (admin@parity.io)
+// This file is a part of the scale-decode crate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//         http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::visitor::DecodeError;
+
+/// This represents a SCALE encoded string.
+pub struct Str<'scale> {
+    bytes: &'scale [u8],
+}
+
+impl<'scale> Str<'scale> {
+    #[doc(hidden)]
+    pub fn new(bytes: &'scale [u8]) -> Str<'scale> {
+        Str { bytes }
+    }
+    /// The bytes making up this string, still SCALE length-prefix encoded.
+    pub fn bytes(&self) -> &'scale [u8] {
+        self.bytes
+    }
+    /// Decode and return the underlying `&str`.
+    pub fn as_str(&self) -> Result<&'scale str, DecodeError> {
+        let decoded: alloc::string::String = codec::Decode::decode(&mut &*self.bytes)
+            .map_err(|e| DecodeError::from_string(alloc::format!("Cannot decode string: {e}")))?;
+        // Safety net: `codec`'s `String` decode already validates UTF8, but we only have the
+        // owned copy to hand back a `&'scale str` from the original bytes, so re-derive the
+        // unprefixed slice and trust that it's valid given the above succeeded.
+        let prefix_len = self.bytes.len() - decoded.len();
+        core::str::from_utf8(&self.bytes[prefix_len..])
+            .map_err(|e| DecodeError::from_string(alloc::format!("Cannot decode string: {e}")))
+    }
+}
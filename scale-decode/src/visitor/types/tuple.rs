@@ -0,0 +1,91 @@
+// Copyright (C) 2023 Parity Technologies (UK) Ltd. (admin@parity.io)
+// This file is a part of the scale-decode crate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//         http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    visitor::{DecodeError, IgnoreVisitor, Visitor},
+    TypeResolver,
+};
+use alloc::vec::Vec;
+use scale_info::PortableRegistry;
+
+/// This represents a tuple of values.
+pub struct Tuple<'scale, 'info, R: TypeResolver = PortableRegistry> {
+    bytes: &'scale [u8],
+    item_bytes: &'scale [u8],
+    type_ids: Vec<R::TypeId>,
+    position: usize,
+    types: &'info R,
+}
+
+impl<'scale, 'info, R: TypeResolver> Tuple<'scale, 'info, R> {
+    #[doc(hidden)]
+    pub fn new(
+        bytes: &'scale [u8],
+        type_ids: Vec<R::TypeId>,
+        types: &'info R,
+    ) -> Tuple<'scale, 'info, R> {
+        Tuple { bytes, item_bytes: bytes, type_ids, position: 0, types }
+    }
+    /// Skip over all bytes associated with this tuple.
+    pub fn skip_decoding(&mut self) -> Result<(), DecodeError> {
+        while self.position < self.type_ids.len() {
+            self.decode_item(IgnoreVisitor::default()).transpose()?;
+        }
+        Ok(())
+    }
+    /// The bytes representing this tuple and anything following it.
+    pub fn bytes_from_start(&self) -> &'scale [u8] {
+        self.bytes
+    }
+    /// The bytes that have not yet been decoded in this tuple.
+    pub fn bytes_from_undecoded(&self) -> &'scale [u8] {
+        self.item_bytes
+    }
+    /// The number of un-decoded items remaining in this tuple.
+    pub fn remaining(&self) -> usize {
+        self.type_ids.len() - self.position
+    }
+    /// Decode the next value in the tuple by providing a visitor to handle it.
+    pub fn decode_item<V: Visitor<TypeResolver = R>>(
+        &mut self,
+        visitor: V,
+    ) -> Option<Result<V::Value<'scale, 'info>, V::Error>> {
+        if self.position >= self.type_ids.len() {
+            return None;
+        }
+
+        let type_id = self.type_ids[self.position].clone();
+        let b = &mut &*self.item_bytes;
+
+        let res = crate::visitor::decode_with_visitor(b, type_id, self.types, visitor);
+
+        self.item_bytes = *b;
+        self.position += 1;
+
+        Some(res)
+    }
+}
+
+impl<'scale, 'info, R: TypeResolver> crate::visitor::DecodeItemIterator<'scale, 'info>
+    for Tuple<'scale, 'info, R>
+{
+    type TypeResolver = R;
+    fn decode_item<'a, V: Visitor<TypeResolver = R>>(
+        &mut self,
+        visitor: V,
+    ) -> Option<Result<V::Value<'scale, 'info>, V::Error>> {
+        self.decode_item(visitor)
+    }
+}
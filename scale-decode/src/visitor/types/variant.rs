@@ -0,0 +1,82 @@
+// Copyright (C) 2023 Parity Technologies (UK) Ltd. (admin@parity.io)
+// This file is a part of the scale-decode crate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//         http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::Composite;
+use crate::{
+    type_resolver::VariantInfo,
+    visitor::{DecodeError, Visitor},
+    TypeResolver,
+};
+use scale_info::PortableRegistry;
+
+/// This represents a variant type.
+pub struct Variant<'scale, 'info, R: TypeResolver = PortableRegistry> {
+    bytes: &'scale [u8],
+    variant: VariantInfo<R::TypeId>,
+    fields: Composite<'scale, 'info, R>,
+}
+
+impl<'scale, 'info, R: TypeResolver> Variant<'scale, 'info, R> {
+    #[doc(hidden)]
+    pub fn new(
+        bytes: &'scale [u8],
+        variant: VariantInfo<R::TypeId>,
+        fields: Composite<'scale, 'info, R>,
+    ) -> Variant<'scale, 'info, R> {
+        Variant { bytes, variant, fields }
+    }
+    /// The name of the variant.
+    pub fn name(&self) -> &str {
+        &self.variant.name
+    }
+    /// The index of the variant.
+    pub fn index(&self) -> u8 {
+        self.variant.index
+    }
+    /// The bytes representing this variant and anything following it.
+    pub fn bytes_from_start(&self) -> &'scale [u8] {
+        self.bytes
+    }
+    /// The fields contained in this variant, which can be decoded like a [`Composite`] type.
+    pub fn fields(&mut self) -> &mut Composite<'scale, 'info, R> {
+        &mut self.fields
+    }
+    /// Skip over all bytes associated with this variant.
+    pub fn skip_decoding(&mut self) -> Result<(), DecodeError> {
+        self.fields.skip_decoding()
+    }
+    /// Decode the next field in the variant by providing a visitor to handle it.
+    pub fn decode_item<V: Visitor<TypeResolver = R>>(
+        &mut self,
+        visitor: V,
+    ) -> Option<Result<V::Value<'scale, 'info>, V::Error>> {
+        self.fields.decode_item(visitor)
+    }
+}
+
+/// A single field in a variant.
+pub type VariantField<'scale, 'info, R> = super::CompositeField<'scale, 'info, R>;
+
+impl<'scale, 'info, R: TypeResolver> crate::visitor::DecodeItemIterator<'scale, 'info>
+    for Variant<'scale, 'info, R>
+{
+    type TypeResolver = R;
+    fn decode_item<'a, V: Visitor<TypeResolver = R>>(
+        &mut self,
+        visitor: V,
+    ) -> Option<Result<V::Value<'scale, 'info>, V::Error>> {
+        self.decode_item(visitor)
+    }
+}